@@ -0,0 +1,219 @@
+//! A configurable builder for generating a single word from a
+//! [`ProbabilityTable`], with control over its length, a starting prefix,
+//! and what to do when a suffix has no known continuation — instead of the
+//! `table.get(key).unwrap()` panic in `ProbabilityTable::generate_words`.
+
+use std::fmt;
+
+use rand::prelude::*;
+
+use crate::ProbabilityTable;
+
+/// What [`WordGenerator`] should do when the current suffix has no known
+/// continuation in the table, e.g. because it never occurred in the
+/// training corpus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FallbackPolicy {
+    /// Stop generating and keep whatever has been produced so far.
+    Stop,
+    /// Fail with [`GenerationError::NoContinuation`].
+    Fail,
+}
+
+/// Errors produced by [`WordGenerator::generate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerationError {
+    /// The table has no continuation for the given suffix, and the
+    /// fallback policy is [`FallbackPolicy::Fail`].
+    NoContinuation(String),
+    /// No word satisfying the configured length bounds could be produced
+    /// within `max_attempts` tries.
+    LengthConstraintUnsatisfiable,
+}
+
+impl fmt::Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerationError::NoContinuation(key) => {
+                write!(f, "no known continuation for {:?}", key)
+            }
+            GenerationError::LengthConstraintUnsatisfiable => write!(
+                f,
+                "could not generate a word matching the length constraints"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GenerationError {}
+
+/// A builder that wraps a [`ProbabilityTable`] and configures how a single
+/// word is generated from it.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::io::BufReader;
+/// use word_generator::{generator::WordGenerator, ProbabilityTable};
+///
+/// let reader = BufReader::new(
+///     "bonjour monde chat chien maison voiture arbre fleur soleil lune etoile".as_bytes(),
+/// );
+/// let table = ProbabilityTable::from_reader(reader, 3)?;
+///
+/// let word = WordGenerator::new(&table).min_len(4).max_len(8).generate()?;
+/// assert!(word.len() >= 4 && word.len() <= 8);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WordGenerator<'a> {
+    table: &'a ProbabilityTable,
+    min_len: usize,
+    max_len: usize,
+    prefix: Option<String>,
+    fallback: FallbackPolicy,
+    max_attempts: usize,
+}
+
+impl<'a> WordGenerator<'a> {
+    /// Create a new builder over `table` with no length bounds, no starting
+    /// prefix, and [`FallbackPolicy::Stop`] as the fallback policy.
+    pub fn new(table: &'a ProbabilityTable) -> WordGenerator<'a> {
+        WordGenerator {
+            table,
+            min_len: 0,
+            max_len: usize::MAX,
+            prefix: None,
+            fallback: FallbackPolicy::Stop,
+            max_attempts: 100,
+        }
+    }
+
+    /// Reject generated words shorter than `min_len`.
+    pub fn min_len(mut self, min_len: usize) -> Self {
+        self.min_len = min_len;
+        self
+    }
+
+    /// Reject generated words longer than `max_len`.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Continue generation from `prefix` instead of starting from an empty
+    /// word.
+    pub fn starting_with(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Set what to do when the current suffix has no known continuation.
+    pub fn fallback(mut self, fallback: FallbackPolicy) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// How many times to retry generation when the length bounds aren't
+    /// met, before giving up with
+    /// [`GenerationError::LengthConstraintUnsatisfiable`].
+    pub fn max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Generate a word using [`thread_rng`].
+    pub fn generate(&self) -> Result<String, GenerationError> {
+        self.generate_with_rng(&mut thread_rng())
+    }
+
+    /// Same as [`generate`](WordGenerator::generate) but lets you supply
+    /// your own [`Rng`] instead of defaulting to [`thread_rng`].
+    pub fn generate_with_rng<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<String, GenerationError> {
+        for _ in 0..self.max_attempts.max(1) {
+            if let Some(word) = self.try_generate_once(rng)? {
+                return Ok(word);
+            }
+        }
+        Err(GenerationError::LengthConstraintUnsatisfiable)
+    }
+
+    fn try_generate_once<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<Option<String>, GenerationError> {
+        let accuracy = self.table.accuracy;
+        let mut out = match &self.prefix {
+            Some(prefix) => format!("{}{}", " ".repeat(accuracy), prefix.to_lowercase()),
+            None => " ".repeat(accuracy),
+        };
+
+        loop {
+            let key = crate::suffix_key(&out, accuracy);
+            let choices = match self.table.table.get(&key) {
+                Some(choices) => choices,
+                None => match self.fallback {
+                    FallbackPolicy::Stop => break,
+                    FallbackPolicy::Fail => return Err(GenerationError::NoContinuation(key)),
+                },
+            };
+            let next_letter = crate::weighted_sample_char(choices, rng);
+            out += &next_letter.to_string();
+
+            if out.trim().len() > self.max_len {
+                return Ok(None);
+            }
+            if out.ends_with(' ') {
+                break;
+            }
+        }
+
+        let word = out.trim().to_string();
+        if word.len() >= self.min_len {
+            Ok(Some(word))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(feature = "french")]
+    fn generate_respects_min_and_max_len() -> std::io::Result<()> {
+        use super::*;
+        use std::io::BufReader;
+
+        let reader = BufReader::new(crate::langs::fr_txt());
+        let table = ProbabilityTable::from_reader(reader, 3)?;
+
+        for _ in 0..20 {
+            let word = WordGenerator::new(&table).min_len(4).max_len(8).generate().unwrap();
+            assert!(word.len() >= 4 && word.len() <= 8);
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "french")]
+    fn generate_lowercases_starting_with_prefix() -> std::io::Result<()> {
+        use super::*;
+        use std::io::BufReader;
+
+        let reader = BufReader::new(crate::langs::fr_txt());
+        let table = ProbabilityTable::from_reader(reader, 3)?;
+
+        let word = WordGenerator::new(&table)
+            .starting_with("Bo")
+            .generate()
+            .unwrap();
+        assert!(word.starts_with("bo"));
+        Ok(())
+    }
+}
@@ -1,8 +1,112 @@
 //! This module contains a list of lists of word of every word of a
 //! language. See the Licences section of the main module for more information
 //! about the licence of each lists
+//!
+//! Each language is gated behind its own Cargo feature (e.g. `french`,
+//! `english`) so that a binary only embeds the word lists it actually uses.
 
 /// Every word of the french language with one word per line
-pub const FR_TXT: &'static [u8] = _FR_TXT;
+#[cfg(feature = "french")]
+pub const FR_TXT: &[u8] = _FR_TXT;
 
-const _FR_TXT: &'static [u8] = include_bytes!("Fr.txt");
+#[cfg(feature = "french")]
+const _FR_TXT: &[u8] = include_bytes!("Fr.txt");
+
+/// Every word of the english language with one word per line
+#[cfg(feature = "english")]
+pub const EN_TXT: &[u8] = include_bytes!("En.txt");
+
+/// Every word of the italian language with one word per line
+#[cfg(feature = "italian")]
+pub const IT_TXT: &[u8] = include_bytes!("It.txt");
+
+/// Every word of the spanish language with one word per line
+#[cfg(feature = "spanish")]
+pub const ES_TXT: &[u8] = include_bytes!("Es.txt");
+
+/// Every word of the german language with one word per line
+#[cfg(feature = "german")]
+pub const DE_TXT: &[u8] = include_bytes!("De.txt");
+
+/// Every word of the french language with one word per line.
+///
+/// Equivalent to [`FR_TXT`], provided as a function so it reads the same
+/// as the other accessors in doc examples.
+#[cfg(feature = "french")]
+pub fn fr_txt() -> &'static [u8] {
+    FR_TXT
+}
+
+/// Every word of the english language with one word per line.
+#[cfg(feature = "english")]
+pub fn en_txt() -> &'static [u8] {
+    EN_TXT
+}
+
+/// Every word of the italian language with one word per line.
+#[cfg(feature = "italian")]
+pub fn it_txt() -> &'static [u8] {
+    IT_TXT
+}
+
+/// Every word of the spanish language with one word per line.
+#[cfg(feature = "spanish")]
+pub fn es_txt() -> &'static [u8] {
+    ES_TXT
+}
+
+/// Every word of the german language with one word per line.
+#[cfg(feature = "german")]
+pub fn de_txt() -> &'static [u8] {
+    DE_TXT
+}
+
+/// A built-in, feature-gated language corpus.
+///
+/// Each variant is only available when its matching Cargo feature is
+/// enabled, e.g. [`Language::Italian`] requires the `italian` feature.
+/// This lets [`ProbabilityTable::from_language`](crate::ProbabilityTable::from_language)
+/// pick a bundled corpus without pulling in every language's word list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    #[cfg(feature = "french")]
+    French,
+    #[cfg(feature = "english")]
+    English,
+    #[cfg(feature = "italian")]
+    Italian,
+    #[cfg(feature = "spanish")]
+    Spanish,
+    #[cfg(feature = "german")]
+    German,
+}
+
+impl Language {
+    /// The embedded word list for this language, one word per line.
+    pub fn txt(self) -> &'static [u8] {
+        match self {
+            #[cfg(feature = "french")]
+            Language::French => FR_TXT,
+            #[cfg(feature = "english")]
+            Language::English => EN_TXT,
+            #[cfg(feature = "italian")]
+            Language::Italian => IT_TXT,
+            #[cfg(feature = "spanish")]
+            Language::Spanish => ES_TXT,
+            #[cfg(feature = "german")]
+            Language::German => DE_TXT,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(feature = "french")]
+    fn language_txt_matches_its_const() {
+        use super::*;
+
+        assert_eq!(Language::French.txt(), FR_TXT);
+        assert_eq!(fr_txt(), FR_TXT);
+    }
+}
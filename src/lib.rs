@@ -20,17 +20,18 @@
 //!
 //! ```
 //! # fn main() -> std::io::Result<()> {
-//! use std::{fs::File, io::BufReader};
-//! use word_generator::{langs, *};
+//! use std::io::BufReader;
+//! use word_generator::*;
 //!
 //! // let reader = BufReader::new(File::open("Your_lang.txt")?); // using your language
-//! let reader = BufReader::new(langs::fr_txt()); // or a preexisting language
+//! // let reader = BufReader::new(langs::fr_txt()); // or a bundled language (needs its Cargo feature)
+//! let reader = BufReader::new("bonjour monde\nchat chien maison voiture\narbre fleur soleil lune etoile\n".as_bytes());
 //!
 //! // This
 //! let table = ProbabilityTable::from_reader(reader, 3)?;
 //! println!("{:?}", table.generate_words(15)); // Generate 15 word
 //!
-//! # let reader = BufReader::new(langs::fr_txt());
+//! # let reader = BufReader::new("bonjour monde\nchat chien maison voiture\narbre fleur soleil lune etoile\n".as_bytes());
 //!
 //! // Is the same as this
 //! println!("{:?}", generate_words(reader, 3, 15)?);
@@ -46,14 +47,18 @@
 //! If you have more language to add please submit a PR at
 //! [the GitHub of this project](https://github.com/rokonio/word_generator)
 use std::{
-    collections::HashMap,
-    io::{self, prelude::*},
+    collections::{HashMap, HashSet},
+    io::{self, prelude::*, BufReader},
 };
 
 use rand::distributions::WeightedIndex;
 use rand::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+pub mod generator;
 pub mod langs;
+pub mod word_chain;
 /// This is a
 /// [Markov Chains](https://en.wikipedia.org/wiki/Markov_chain#Examples) under
 /// the name of `ProbabilityTable`. It represents the likehood of each
@@ -65,9 +70,9 @@ pub mod langs;
 /// ```
 /// # fn main() -> std::io::Result<()> {
 /// use std::io::BufReader;
-/// use word_generator::{langs, *};
+/// use word_generator::*;
 ///
-/// let reader = BufReader::new(langs::fr_txt());
+/// let reader = BufReader::new("bonjour monde\nchat chien maison voiture\narbre fleur soleil lune etoile\n".as_bytes());
 ///
 /// let table = ProbabilityTable::from_reader(reader, 3)?;
 /// println!("{:?}", table.generate_words(15)); // Generate 15 word
@@ -75,9 +80,11 @@ pub mod langs;
 /// # }
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProbabilityTable {
     pub(crate) table: HashMap<String, HashMap<char, u32>>,
     pub(crate) accuracy: usize,
+    pub(crate) real_words: HashSet<String>,
 }
 
 impl ProbabilityTable {
@@ -85,6 +92,7 @@ impl ProbabilityTable {
         ProbabilityTable {
             table: HashMap::new(),
             accuracy,
+            real_words: HashSet::new(),
         }
     }
 
@@ -99,6 +107,64 @@ impl ProbabilityTable {
         Ok(generate_table(add_space(reader, accuracy)?, accuracy))
     }
 
+    /// Deserialize a `ProbabilityTable` that was previously written with
+    /// [`to_writer`](ProbabilityTable::to_writer), skipping the
+    /// `from_reader` corpus scan entirely.
+    ///
+    /// Requires the `serde` feature.
+    #[cfg(feature = "serde")]
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<ProbabilityTable> {
+        serde_json::from_slice(bytes).map_err(io::Error::from)
+    }
+
+    /// Serialize this table so it can later be restored with
+    /// [`from_bytes`](ProbabilityTable::from_bytes) instead of re-training
+    /// from a corpus.
+    ///
+    /// Requires the `serde` feature.
+    #[cfg(feature = "serde")]
+    pub fn to_writer(&self, writer: impl Write) -> io::Result<()> {
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
+    /// Construct a new `ProbabilityTable` from one of the bundled
+    /// [`langs::Language`] corpora, without having to open a file yourself.
+    ///
+    /// # Panic
+    ///
+    /// Panic if `accuracy` is less than one.
+    pub fn from_language(language: langs::Language, accuracy: usize) -> io::Result<ProbabilityTable> {
+        ProbabilityTable::from_reader(BufReader::new(language.txt()), accuracy)
+    }
+
+    /// Train further on more text, folding the n-gram counts from `reader`
+    /// into the ones already in this table instead of rebuilding from
+    /// scratch. Useful to stream in several corpora over time.
+    pub fn learn(&mut self, reader: impl BufRead) -> io::Result<()> {
+        let spaced_file = add_space(reader, self.accuracy)?;
+        count_ngrams(&mut self.table, &spaced_file, self.accuracy);
+        self.real_words
+            .extend(spaced_file.split_whitespace().map(str::to_string));
+        Ok(())
+    }
+
+    /// Sum the n-gram counts of `other` into this table, e.g. to blend two
+    /// languages together by merging several tables trained separately.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `self.accuracy != other.accuracy`.
+    pub fn merge(&mut self, other: &ProbabilityTable) {
+        assert_eq!(self.accuracy, other.accuracy);
+        for (key, chars) in &other.table {
+            let entry = self.table.entry(key.clone()).or_default();
+            for (letter, count) in chars {
+                *entry.entry(*letter).or_default() += count;
+            }
+        }
+        self.real_words.extend(other.real_words.iter().cloned());
+    }
+
     /// Generate `amount` words.
     ///
     /// # Example
@@ -106,9 +172,9 @@ impl ProbabilityTable {
     /// ```
     /// # fn main() -> std::io::Result<()> {
     /// use std::io::BufReader;
-    /// use word_generator::{langs, *};
+    /// use word_generator::*;
     ///
-    /// let reader = BufReader::new(langs::fr_txt());
+    /// let reader = BufReader::new("bonjour monde\nchat chien maison voiture\narbre fleur soleil lune etoile\n".as_bytes());
     ///
     /// let table = ProbabilityTable::from_reader(reader, 3)?;
     /// println!("{:?}", table.generate_words(15)); // Generate 15 word
@@ -116,7 +182,100 @@ impl ProbabilityTable {
     /// # }
     /// ```
     pub fn generate_words(&self, amount: u32) -> Vec<String> {
-        generate_multiple_words(self, amount)
+        generate_multiple_words(self, amount, &mut thread_rng())
+    }
+
+    /// Same as [`generate_words`](ProbabilityTable::generate_words) but lets
+    /// you supply your own [`Rng`] instead of defaulting to [`thread_rng`].
+    ///
+    /// This is useful to get reproducible output, for example by seeding a
+    /// `rand_chacha::ChaCha20Rng` from a `u64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    /// use std::io::BufReader;
+    /// use word_generator::*;
+    ///
+    /// let reader = BufReader::new("bonjour monde\nchat chien maison voiture\narbre fleur soleil lune etoile\n".as_bytes());
+    ///
+    /// let table = ProbabilityTable::from_reader(reader, 3)?;
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// println!("{:?}", table.generate_words_with_rng(15, &mut rng));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn generate_words_with_rng<R: Rng + ?Sized>(&self, amount: u32, rng: &mut R) -> Vec<String> {
+        generate_multiple_words(self, amount, rng)
+    }
+
+    /// Generate `amount` words following `options`, e.g. to reject any
+    /// generated word that collides with a real word seen while training
+    /// this table, or with a word already generated in this batch.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use std::io::BufReader;
+    /// use word_generator::*;
+    ///
+    /// let reader = BufReader::new("bonjour monde\nchat chien maison voiture\narbre fleur soleil lune etoile\n".as_bytes());
+    ///
+    /// let table = ProbabilityTable::from_reader(reader, 3)?;
+    /// let options = GenerationOptions {
+    ///     forbid_real_words: true,
+    ///     unique: true,
+    ///     ..Default::default()
+    /// };
+    /// println!("{:?}", table.generate_words_with_options(15, options));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn generate_words_with_options(&self, amount: u32, options: GenerationOptions) -> Vec<String> {
+        generate_multiple_words_with_options(self, amount, options, &mut thread_rng())
+    }
+
+    /// Same as
+    /// [`generate_words_with_options`](ProbabilityTable::generate_words_with_options)
+    /// but lets you supply your own [`Rng`] instead of defaulting to
+    /// [`thread_rng`].
+    pub fn generate_words_with_options_and_rng<R: Rng + ?Sized>(
+        &self,
+        amount: u32,
+        options: GenerationOptions,
+        rng: &mut R,
+    ) -> Vec<String> {
+        generate_multiple_words_with_options(self, amount, options, rng)
+    }
+}
+
+/// Configuration for
+/// [`ProbabilityTable::generate_words_with_options`], to guard against
+/// generated words that aren't actually novel.
+#[derive(Debug, Clone, Copy)]
+pub struct GenerationOptions {
+    /// Resample a word if it matches a real word seen in the training
+    /// corpus.
+    pub forbid_real_words: bool,
+    /// Resample a word if it has already been generated earlier in the
+    /// same batch.
+    pub unique: bool,
+    /// How many times to resample a rejected word before giving up and
+    /// keeping it anyway.
+    pub max_retries: usize,
+}
+
+impl Default for GenerationOptions {
+    fn default() -> GenerationOptions {
+        GenerationOptions {
+            forbid_real_words: false,
+            unique: false,
+            max_retries: 10,
+        }
     }
 }
 
@@ -133,6 +292,17 @@ fn add_space(reader: impl BufRead, accuracy: usize) -> io::Result<String> {
 // Generate a ProbabilityTable from the output of add_space
 fn generate_table(spaced_file: String, accuracy: usize) -> ProbabilityTable {
     let mut table = ProbabilityTable::new(accuracy);
+    count_ngrams(&mut table.table, &spaced_file, accuracy);
+    table
+        .real_words
+        .extend(spaced_file.split_whitespace().map(str::to_string));
+    table
+}
+
+// Count each n-gram (of length `accuracy`) and the character following it,
+// folding the counts into `table`. Shared by `generate_table` and `learn` so
+// that training a table from scratch and training it incrementally agree.
+fn count_ngrams(table: &mut HashMap<String, HashMap<char, u32>>, spaced_file: &str, accuracy: usize) {
     let chars_list: Vec<_> = spaced_file.chars().collect();
     for charactere in 0..chars_list.len() - accuracy {
         let key: String = chars_list
@@ -143,28 +313,37 @@ fn generate_table(spaced_file: String, accuracy: usize) -> ProbabilityTable {
 
         let value: char = *chars_list.get(charactere + accuracy).unwrap();
 
-        *table
-            .table
-            .entry(key)
-            .or_default()
-            .entry(value)
-            .or_default() += 1;
+        *table.entry(key).or_default().entry(value).or_default() += 1;
     }
-    table
+}
+
+// The accuracy-character lookup key formed by the tail of `out`. Shared by
+// `generate_word` and `generator::WordGenerator` so the two steppers agree
+// on how a suffix is turned into a table key.
+pub(crate) fn suffix_key(out: &str, accuracy: usize) -> String {
+    let chars_list: Vec<_> = out.chars().collect();
+    chars_list[chars_list.len() - accuracy..].iter().collect()
+}
+
+// Sample one character from `choices`, weighted by how often it followed
+// the n-gram during training. Shared by `generate_word` and
+// `generator::WordGenerator`. Choices are taken in character order rather
+// than `HashMap` iteration order, so a seeded `rng` gives the same result on
+// any table with the same counts.
+pub(crate) fn weighted_sample_char<R: Rng + ?Sized>(choices: &HashMap<char, u32>, rng: &mut R) -> char {
+    let mut choices: Vec<(&char, &u32)> = choices.iter().collect();
+    choices.sort_unstable_by_key(|(letter, _)| **letter);
+    let weight = WeightedIndex::new(choices.iter().map(|(_, count)| **count)).unwrap();
+    *choices[weight.sample(rng)].0
 }
 
 // Generate one word from a ProbabilityTable
-fn generate_word(table: &ProbabilityTable, rng: &mut ThreadRng) -> String {
+fn generate_word<R: Rng + ?Sized>(table: &ProbabilityTable, rng: &mut R) -> String {
     let mut out = " ".repeat(table.accuracy);
     loop {
-        let chars_list: Vec<_> = out.chars().collect();
-        let key = &chars_list[chars_list.len() - table.accuracy..]
-            .iter()
-            .collect::<String>();
-        let choices = table.table.get(key).unwrap();
-        let weight = WeightedIndex::new(choices.values()).unwrap();
-        let next_letter = choices.keys().collect::<Vec<&char>>()[weight.sample(rng)];
-        out += &next_letter.to_string();
+        let key = suffix_key(&out, table.accuracy);
+        let choices = table.table.get(&key).unwrap();
+        out += &weighted_sample_char(choices, rng).to_string();
         if out.ends_with(' ') {
             break;
         }
@@ -172,11 +351,40 @@ fn generate_word(table: &ProbabilityTable, rng: &mut ThreadRng) -> String {
     out.trim().to_string()
 }
 
-fn generate_multiple_words(matrix: &ProbabilityTable, number: u32) -> Vec<String> {
+fn generate_multiple_words<R: Rng + ?Sized>(
+    matrix: &ProbabilityTable,
+    number: u32,
+    rng: &mut R,
+) -> Vec<String> {
     let mut vec_string = Vec::new();
-    let mut rng = thread_rng();
     for _ in 0..number {
-        vec_string.push(generate_word(&matrix, &mut rng));
+        vec_string.push(generate_word(matrix, rng));
+    }
+    vec_string
+}
+
+fn generate_multiple_words_with_options<R: Rng + ?Sized>(
+    table: &ProbabilityTable,
+    number: u32,
+    options: GenerationOptions,
+    rng: &mut R,
+) -> Vec<String> {
+    let mut already_generated = HashSet::new();
+    let mut vec_string = Vec::new();
+    for _ in 0..number {
+        let mut word = generate_word(table, rng);
+        let mut retries = 0;
+        while retries < options.max_retries
+            && ((options.forbid_real_words && table.real_words.contains(&word))
+                || (options.unique && already_generated.contains(&word)))
+        {
+            word = generate_word(table, rng);
+            retries += 1;
+        }
+        if options.unique {
+            already_generated.insert(word.clone());
+        }
+        vec_string.push(word);
     }
     vec_string
 }
@@ -189,9 +397,9 @@ fn generate_multiple_words(matrix: &ProbabilityTable, number: u32) -> Vec<String
 /// ```
 /// # fn main() -> std::io::Result<()> {
 /// use std::io::BufReader;
-/// use word_generator::{langs, *};
+/// use word_generator::*;
 ///
-/// let reader = BufReader::new(langs::fr_txt());
+/// let reader = BufReader::new("bonjour monde\nchat chien maison voiture\narbre fleur soleil lune etoile\n".as_bytes());
 ///
 /// println!("{:?}", generate_words(reader, 3, 15)?);
 /// # Ok(())
@@ -201,10 +409,40 @@ pub fn generate_words(
     reader: impl BufRead,
     accuracy: usize,
     amout: u32,
+) -> io::Result<Vec<String>> {
+    generate_words_with_rng(reader, accuracy, amout, &mut thread_rng())
+}
+
+/// Same as [`generate_words`] but lets you supply your own [`Rng`] instead of
+/// defaulting to [`thread_rng`], so the resulting word list is reproducible
+/// from a seed.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+/// use std::io::BufReader;
+/// use word_generator::*;
+///
+/// let reader = BufReader::new("bonjour monde\nchat chien maison voiture\narbre fleur soleil lune etoile\n".as_bytes());
+///
+/// let mut rng = StdRng::seed_from_u64(42);
+/// println!("{:?}", generate_words_with_rng(reader, 3, 15, &mut rng)?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn generate_words_with_rng<R: Rng + ?Sized>(
+    reader: impl BufRead,
+    accuracy: usize,
+    amout: u32,
+    rng: &mut R,
 ) -> io::Result<Vec<String>> {
     let mut out = generate_multiple_words(
         &generate_table(add_space(reader, accuracy)?, accuracy),
         amout,
+        rng,
     );
     out.sort_by_key(|a| a.len());
     Ok(out)
@@ -216,6 +454,7 @@ mod tests {
     use std::io::{self, BufReader};
 
     #[test]
+    #[cfg(feature = "french")]
     fn table_generate_words_works() -> io::Result<()> {
         let reader = BufReader::new(langs::fr_txt());
         let table = ProbabilityTable::from_reader(reader, 3)?;
@@ -224,6 +463,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "french")]
     fn table_consticency() -> io::Result<()> {
         let reader = BufReader::new(langs::fr_txt());
         let table = ProbabilityTable::from_reader(reader, 3)?;
@@ -242,4 +482,106 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn learn_folds_counts_into_existing_table() -> io::Result<()> {
+        let text = "aaa bbb aaa bbb\n";
+        let mut table = ProbabilityTable::from_reader(BufReader::new(text.as_bytes()), 1)?;
+        let once = table.table.clone();
+        table.learn(BufReader::new(text.as_bytes()))?;
+        for (key, chars) in &once {
+            for (letter, count) in chars {
+                assert_eq!(table.table[key][letter], count * 2);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn merge_sums_counts_from_other_table() -> io::Result<()> {
+        let text = "aaa bbb aaa bbb\n";
+        let a = ProbabilityTable::from_reader(BufReader::new(text.as_bytes()), 1)?;
+        let b = ProbabilityTable::from_reader(BufReader::new(text.as_bytes()), 1)?;
+        let mut merged = a.clone();
+        merged.merge(&b);
+        for (key, chars) in &a.table {
+            for (letter, count) in chars {
+                assert_eq!(merged.table[key][letter], count * 2);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "french"))]
+    fn table_round_trips_through_bytes() -> io::Result<()> {
+        let reader = BufReader::new(langs::fr_txt());
+        let table = ProbabilityTable::from_reader(reader, 3)?;
+
+        let mut bytes = Vec::new();
+        table.to_writer(&mut bytes)?;
+        let restored = ProbabilityTable::from_bytes(&bytes)?;
+
+        assert_eq!(restored.accuracy, table.accuracy);
+        assert_eq!(restored.table.len(), table.table.len());
+        assert_eq!(restored.real_words, table.real_words);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "french")]
+    fn generate_words_with_options_respects_unique() -> io::Result<()> {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let reader = BufReader::new(langs::fr_txt());
+        let table = ProbabilityTable::from_reader(reader, 2)?;
+        let options = GenerationOptions {
+            unique: true,
+            max_retries: 1000,
+            ..Default::default()
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+        let words = table.generate_words_with_options_and_rng(10, options, &mut rng);
+        let unique_count = words.iter().collect::<HashSet<_>>().len();
+        assert_eq!(unique_count, words.len());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "french")]
+    fn generate_words_with_options_respects_forbid_real_words() -> io::Result<()> {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let reader = BufReader::new(langs::fr_txt());
+        let table = ProbabilityTable::from_reader(reader, 2)?;
+        let options = GenerationOptions {
+            forbid_real_words: true,
+            max_retries: 1000,
+            ..Default::default()
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+        let words = table.generate_words_with_options_and_rng(10, options, &mut rng);
+        assert!(words.iter().all(|w| !table.real_words.contains(w)));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "french")]
+    fn generate_words_with_rng_is_reproducible() -> io::Result<()> {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let reader = BufReader::new(langs::fr_txt());
+        let table = ProbabilityTable::from_reader(reader, 3)?;
+
+        let mut rng_a = StdRng::seed_from_u64(42);
+        let mut rng_b = StdRng::seed_from_u64(42);
+        assert_eq!(
+            table.generate_words_with_rng(15, &mut rng_a),
+            table.generate_words_with_rng(15, &mut rng_b)
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,137 @@
+//! Word-level Markov chains, for generating whole pseudo-sentences instead
+//! of single words.
+//!
+//! Where [`ProbabilityTable`](crate::ProbabilityTable) predicts the next
+//! *character* from the previous `accuracy` characters, [`WordChain`]
+//! predicts the next *word* from the previous `order` words, which is
+//! useful to produce lorem-ipsum-style filler text that reads like the
+//! source corpus.
+
+use std::collections::HashMap;
+use std::io::{self, prelude::*};
+
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+
+/// A word-level Markov chain built from a sample text.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use std::io::BufReader;
+/// use word_generator::word_chain::WordChain;
+///
+/// let reader = BufReader::new("le chat mange le chien mange le chat dort\n".as_bytes());
+///
+/// let chain = WordChain::from_reader(reader, 2)?;
+/// println!("{}", chain.generate_sentence(20));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WordChain {
+    table: HashMap<Vec<String>, HashMap<String, u32>>,
+    order: usize,
+}
+
+impl WordChain {
+    fn new(order: usize) -> WordChain {
+        WordChain {
+            table: HashMap::new(),
+            order,
+        }
+    }
+
+    /// Construct a new `WordChain` from `reader`, tokenizing on whitespace
+    /// and keying each word on the `order` words preceding it.
+    ///
+    /// # Panic
+    ///
+    /// Panic if `order` is less than one.
+    pub fn from_reader(reader: impl BufRead, order: usize) -> io::Result<WordChain> {
+        assert!(order >= 1);
+        let mut chain = WordChain::new(order);
+        let words = reader
+            .lines()
+            .collect::<io::Result<Vec<String>>>()?
+            .join(" ")
+            .to_lowercase()
+            .split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<String>>();
+
+        if words.len() > order {
+            for window in words.windows(order + 1) {
+                let key = window[..order].to_vec();
+                let value = window[order].clone();
+                *chain.table.entry(key).or_default().entry(value).or_default() += 1;
+            }
+        }
+        Ok(chain)
+    }
+
+    /// Generate a sentence of at most `max_words` words, starting from a
+    /// randomly chosen `order`-word sequence from the corpus and stopping
+    /// early if a word has no known continuation.
+    ///
+    /// Returns an empty string if this chain was built from a corpus with
+    /// `order` words or fewer, since there is no `order`-word seed to start
+    /// from.
+    pub fn generate_sentence(&self, max_words: usize) -> String {
+        generate_sentence(self, max_words, &mut thread_rng())
+    }
+
+    /// Same as [`generate_sentence`](WordChain::generate_sentence) but lets
+    /// you supply your own [`Rng`] instead of defaulting to [`thread_rng`].
+    pub fn generate_sentence_with_rng<R: Rng + ?Sized>(
+        &self,
+        max_words: usize,
+        rng: &mut R,
+    ) -> String {
+        generate_sentence(self, max_words, rng)
+    }
+}
+
+fn generate_sentence<R: Rng + ?Sized>(chain: &WordChain, max_words: usize, rng: &mut R) -> String {
+    let keys: Vec<&Vec<String>> = chain.table.keys().collect();
+    if keys.is_empty() {
+        return String::new();
+    }
+
+    let mut out = keys[rng.gen_range(0..keys.len())].clone();
+    while out.len() < max_words {
+        let key = &out[out.len() - chain.order..];
+        let choices = match chain.table.get(key) {
+            Some(choices) => choices,
+            None => break,
+        };
+        let weight = WeightedIndex::new(choices.values()).unwrap();
+        let next_word = choices.keys().collect::<Vec<&String>>()[weight.sample(rng)].clone();
+        out.push(next_word);
+    }
+    out.truncate(max_words);
+    out.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    #[test]
+    fn generate_sentence_respects_max_words() -> io::Result<()> {
+        let reader = BufReader::new("one two three four five".as_bytes());
+        let chain = WordChain::from_reader(reader, 3)?;
+        assert_eq!(chain.generate_sentence(1).split_whitespace().count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn generate_sentence_on_short_corpus_does_not_panic() -> io::Result<()> {
+        let reader = BufReader::new("one two three".as_bytes());
+        let chain = WordChain::from_reader(reader, 5)?;
+        assert_eq!(chain.generate_sentence(10), "");
+        Ok(())
+    }
+}